@@ -1,10 +1,17 @@
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct RendererInfo {
     backend: String,
+    requested: String,
     adapter_name: String,
+    device_type: String,
+    driver: String,
     features: String,
+    max_texture_dimension_2d: u32,
+    max_buffer_size: f64,
+    surface_format: Option<String>,
 }
 
 #[wasm_bindgen]
@@ -14,15 +21,300 @@ impl RendererInfo {
         self.backend.clone()
     }
 
+    /// The backend the caller asked for: `"WebGpu"`, `"WebGl"` or `"Auto"`.
+    ///
+    /// Useful for troubleshooting: it tells whether a given renderer was forced
+    /// (e.g. via `?renderer=`) or picked by automatic detection.
+    #[wasm_bindgen(getter)]
+    pub fn requested(&self) -> String {
+        self.requested.clone()
+    }
+
     #[wasm_bindgen(getter)]
     pub fn adapter_name(&self) -> String {
         self.adapter_name.clone()
     }
 
+    /// The adapter's reported `DeviceType`, e.g. `"DiscreteGpu"` or `"Cpu"`.
+    #[wasm_bindgen(getter)]
+    pub fn device_type(&self) -> String {
+        self.device_type.clone()
+    }
+
+    /// The adapter's driver string from `get_info()`, useful for bug reports.
+    #[wasm_bindgen(getter)]
+    pub fn driver(&self) -> String {
+        self.driver.clone()
+    }
+
     #[wasm_bindgen(getter)]
     pub fn features(&self) -> String {
         self.features.clone()
     }
+
+    /// The granted `max_texture_dimension_2d` limit.
+    #[wasm_bindgen(getter)]
+    pub fn max_texture_dimension_2d(&self) -> u32 {
+        self.max_texture_dimension_2d
+    }
+
+    /// The granted `max_buffer_size` limit, in bytes (as `f64` for JS).
+    #[wasm_bindgen(getter)]
+    pub fn max_buffer_size(&self) -> f64 {
+        self.max_buffer_size
+    }
+
+    /// The `TextureFormat` the surface is configured with, or `None` until a
+    /// canvas has been attached via [`Renderer::attach_canvas`].
+    #[wasm_bindgen(getter)]
+    pub fn surface_format(&self) -> Option<String> {
+        self.surface_format.clone()
+    }
+}
+
+/// A surface configured for a particular canvas, kept alongside its
+/// configuration so it can be reconfigured on a resize.
+struct SurfaceState {
+    surface: wgpu::Surface<'static>,
+    config: wgpu::SurfaceConfiguration,
+}
+
+/// A live renderer: an adapter that has negotiated a [`wgpu::Device`] and
+/// [`wgpu::Queue`] and is ready for downstream rendering code to use. A canvas
+/// can be bound with [`Renderer::attach_canvas`] to actually present to the
+/// page.
+#[wasm_bindgen]
+pub struct Renderer {
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    device: wgpu::Device,
+    #[allow(dead_code)]
+    queue: wgpu::Queue,
+    surface: Option<SurfaceState>,
+    info: RendererInfo,
+}
+
+#[wasm_bindgen]
+impl Renderer {
+    #[wasm_bindgen(getter)]
+    pub fn info(&self) -> RendererInfo {
+        self.info.clone()
+    }
+
+    /// Binds `canvas` to this renderer, creating and configuring a surface at
+    /// `width` x `height`.
+    ///
+    /// This creates the surface on the already-selected adapter; if the adapter
+    /// cannot present to the canvas it returns an error. To guarantee a
+    /// presentable adapter, prefer [`initialize_renderer_for_canvas`], which
+    /// feeds the surface as the `compatible_surface` during adapter selection.
+    pub fn attach_canvas(
+        &mut self,
+        canvas: web_sys::HtmlCanvasElement,
+        width: u32,
+        height: u32,
+    ) -> Result<(), JsValue> {
+        let surface = self
+            .instance
+            .create_surface(wgpu::SurfaceTarget::Canvas(canvas))
+            .map_err(|err| JsValue::from_str(&format!("Failed to create surface: {err}")))?;
+
+        self.configure_surface(surface, width, height)
+            .map_err(|err| JsValue::from_str(&err))
+    }
+
+    /// Picks a supported `TextureFormat`/`PresentMode` from the surface
+    /// capabilities (preferring an sRGB format and the always-available `Fifo`
+    /// present mode), configures the surface at `width` x `height`, and records
+    /// the chosen format in [`RendererInfo::surface_format`].
+    fn configure_surface(
+        &mut self,
+        surface: wgpu::Surface<'static>,
+        width: u32,
+        height: u32,
+    ) -> Result<(), String> {
+        let caps = surface.get_capabilities(&self.adapter);
+        if caps.formats.is_empty() {
+            return Err("chosen adapter cannot present to the canvas".to_string());
+        }
+
+        let format = caps
+            .formats
+            .iter()
+            .copied()
+            .find(|format| format.is_srgb())
+            .unwrap_or(caps.formats[0]);
+        let present_mode = if caps.present_modes.contains(&wgpu::PresentMode::Fifo) {
+            wgpu::PresentMode::Fifo
+        } else {
+            caps.present_modes[0]
+        };
+
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: width.max(1),
+            height: height.max(1),
+            present_mode,
+            desired_maximum_frame_latency: 2,
+            alpha_mode: caps.alpha_modes[0],
+            view_formats: vec![],
+        };
+        surface.configure(&self.device, &config);
+
+        self.info.surface_format = Some(format!("{format:?}"));
+        self.surface = Some(SurfaceState { surface, config });
+
+        Ok(())
+    }
+
+    /// Reconfigures the attached surface for a new size, e.g. after a
+    /// device-pixel-ratio or layout change. A no-op with no attached canvas.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if let Some(state) = self.surface.as_mut() {
+            state.config.width = width.max(1);
+            state.config.height = height.max(1);
+            state.surface.configure(&self.device, &state.config);
+        }
+    }
+}
+
+/// Machine-readable category of an initialization failure.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RendererInitErrorKind {
+    /// The browser does not expose `navigator.gpu` (or it is undefined).
+    WebGpuUnavailable,
+    /// No adapter could be produced for the attempted backends, or no surface
+    /// could be created or configured for the supplied canvas.
+    NoAdapter,
+    /// An adapter was found but `request_device` failed.
+    DeviceRequestFailed,
+}
+
+/// A structured, non-panicking initialization failure surfaced to JS so the
+/// embedding app can decide programmatically whether to reload with a
+/// different backend or show a GPU-unsupported message.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct RendererInitError {
+    kind: RendererInitErrorKind,
+    attempted_backends: String,
+    message: String,
+    suggestion: String,
+}
+
+impl RendererInitError {
+    fn new(
+        kind: RendererInitErrorKind,
+        attempted: wgpu::Backends,
+        message: impl Into<String>,
+    ) -> Self {
+        let suggestion = match kind {
+            RendererInitErrorKind::WebGpuUnavailable | RendererInitErrorKind::NoAdapter => {
+                "retry with ?renderer=webgl"
+            }
+            RendererInitErrorKind::DeviceRequestFailed => {
+                "retry with ?renderer=webgl or reduce requested limits"
+            }
+        };
+
+        Self {
+            kind,
+            attempted_backends: format!("{attempted:?}"),
+            message: message.into(),
+            suggestion: suggestion.to_string(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RendererInitError {
+    #[wasm_bindgen(getter)]
+    pub fn kind(&self) -> RendererInitErrorKind {
+        self.kind
+    }
+
+    /// The backends that were attempted, e.g. `"Backends(GL)"`.
+    #[wasm_bindgen(getter)]
+    pub fn attempted_backends(&self) -> String {
+        self.attempted_backends.clone()
+    }
+
+    /// A human-readable description of what went wrong.
+    #[wasm_bindgen(getter)]
+    pub fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    /// A human-readable remediation hint, e.g. `"retry with ?renderer=webgl"`.
+    #[wasm_bindgen(getter)]
+    pub fn suggestion(&self) -> String {
+        self.suggestion.clone()
+    }
+}
+
+/// Returns `true` when the current browser exposes a `navigator.gpu` object.
+///
+/// An undefined (or null) `gpu` property is treated as "unsupported"; some
+/// browsers leave it undefined entirely, and handing `BROWSER_WEBGPU` to
+/// `wgpu` there surfaces as a raw JS `TypeError` rather than a clean `None`
+/// adapter. Reading the property reflectively keeps us off the still-unstable
+/// `web_sys` WebGPU bindings.
+fn is_browser_webgpu_supported() -> bool {
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return false,
+    };
+
+    let navigator = window.navigator();
+    match js_sys::Reflect::get(navigator.as_ref(), &JsValue::from_str("gpu")) {
+        Ok(gpu) => !gpu.is_undefined() && !gpu.is_null(),
+        Err(_) => false,
+    }
+}
+
+fn new_instance(backends: wgpu::Backends) -> wgpu::Instance {
+    wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends,
+        flags: wgpu::InstanceFlags::default(),
+        dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+    })
+}
+
+async fn request_adapter(
+    instance: &wgpu::Instance,
+    compatible_surface: Option<&wgpu::Surface<'static>>,
+) -> Option<wgpu::Adapter> {
+    instance
+        .request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            compatible_surface,
+            force_fallback_adapter: false,
+        })
+        .await
+}
+
+/// Creates a surface for `canvas` on `instance` when a canvas is supplied, so
+/// it can be passed as the `compatible_surface` during adapter selection.
+fn make_surface(
+    instance: &wgpu::Instance,
+    canvas: &Option<web_sys::HtmlCanvasElement>,
+    backends: wgpu::Backends,
+) -> Result<Option<wgpu::Surface<'static>>, RendererInitError> {
+    match canvas {
+        Some(canvas) => instance
+            .create_surface(wgpu::SurfaceTarget::Canvas(canvas.clone()))
+            .map(Some)
+            .map_err(|err| {
+                RendererInitError::new(
+                    RendererInitErrorKind::NoAdapter,
+                    backends,
+                    format!("failed to create surface for canvas: {err}"),
+                )
+            }),
+        None => Ok(None),
+    }
 }
 
 fn selected_backends() -> wgpu::Backends {
@@ -50,32 +342,261 @@ fn selected_backends() -> wgpu::Backends {
     }
 }
 
+/// A runtime backend preference, typically derived from a `?renderer=` query
+/// parameter supplied by the embedding page.
+#[derive(Debug, PartialEq, Eq)]
+enum BackendPreference {
+    WebGpu,
+    WebGl,
+    Auto,
+}
+
+impl BackendPreference {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BackendPreference::WebGpu => "WebGpu",
+            BackendPreference::WebGl => "WebGl",
+            BackendPreference::Auto => "Auto",
+        }
+    }
+}
+
+/// Parses a fuzzy, case-insensitive backend-preference string.
+///
+/// `"webgpu"`/`"gpu"` force WebGPU, `"webgl"`/`"webgl2"`/`"gl"`/`"opengl"`/
+/// `"gles"` force the GL backend, and `"auto"`, an empty string or anything
+/// unrecognized degrade to automatic selection rather than erroring.
+fn parse_preference(preference: Option<String>) -> BackendPreference {
+    match preference {
+        Some(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+            "webgpu" | "gpu" => BackendPreference::WebGpu,
+            "webgl" | "webgl2" | "gl" | "opengl" | "gles" => BackendPreference::WebGl,
+            _ => BackendPreference::Auto,
+        },
+        None => BackendPreference::Auto,
+    }
+}
+
+#[wasm_bindgen]
+pub async fn initialize_renderer() -> Result<Renderer, RendererInitError> {
+    initialize_renderer_with_preference(None).await
+}
+
+/// Like [`initialize_renderer`] but lets the host page force a backend at
+/// runtime (e.g. from a `?renderer=` URL query parameter) instead of relying
+/// only on compile-time features.
+#[wasm_bindgen]
+pub async fn initialize_renderer_with_preference(
+    preference: Option<String>,
+) -> Result<Renderer, RendererInitError> {
+    let preference = parse_preference(preference);
+    let (instance, adapter, _surface) = acquire_adapter(&preference, None).await?;
+    build_renderer(instance, adapter, preference.as_str()).await
+}
+
+/// Initializes a renderer already bound to `canvas`.
+///
+/// Unlike [`initialize_renderer_with_preference`], the canvas surface is
+/// created up front and supplied as the `compatible_surface` during adapter
+/// selection, so the chosen adapter is guaranteed able to present to it. The
+/// surface is then configured at `width` x `height`; use [`Renderer::resize`]
+/// for later device-pixel-ratio or layout changes.
 #[wasm_bindgen]
-pub async fn initialize_renderer() -> Result<RendererInfo, JsValue> {
+pub async fn initialize_renderer_for_canvas(
+    canvas: web_sys::HtmlCanvasElement,
+    width: u32,
+    height: u32,
+    preference: Option<String>,
+) -> Result<Renderer, RendererInitError> {
+    let preference = parse_preference(preference);
+    let (instance, adapter, surface) = acquire_adapter(&preference, Some(canvas)).await?;
+    let attempted = wgpu::Backends::from(adapter.get_info().backend);
+
+    let mut renderer = build_renderer(instance, adapter, preference.as_str()).await?;
+    let surface = surface.ok_or_else(|| {
+        RendererInitError::new(
+            RendererInitErrorKind::NoAdapter,
+            attempted,
+            "no surface was created for the canvas",
+        )
+    })?;
+    renderer
+        .configure_surface(surface, width, height)
+        .map_err(|message| {
+            RendererInitError::new(RendererInitErrorKind::NoAdapter, attempted, message)
+        })?;
+
+    Ok(renderer)
+}
+
+/// Selects an instance and adapter for `preference`, first creating a surface
+/// for `canvas` (when supplied) and feeding it as the `compatible_surface` so
+/// the chosen adapter is guaranteed able to present to the page's canvas.
+async fn acquire_adapter(
+    preference: &BackendPreference,
+    canvas: Option<web_sys::HtmlCanvasElement>,
+) -> Result<(wgpu::Instance, wgpu::Adapter, Option<wgpu::Surface<'static>>), RendererInitError> {
+    match preference {
+        BackendPreference::WebGpu => {
+            let backends = wgpu::Backends::BROWSER_WEBGPU;
+            if !is_browser_webgpu_supported() {
+                return Err(RendererInitError::new(
+                    RendererInitErrorKind::WebGpuUnavailable,
+                    backends,
+                    "navigator.gpu is undefined; this browser does not support WebGPU",
+                ));
+            }
+            select_adapter(backends, &canvas).await?.ok_or_else(|| {
+                RendererInitError::new(
+                    RendererInitErrorKind::NoAdapter,
+                    backends,
+                    "no WebGPU adapter available",
+                )
+            })
+        }
+        BackendPreference::WebGl => {
+            let backends = wgpu::Backends::GL;
+            select_adapter(backends, &canvas).await?.ok_or_else(|| {
+                RendererInitError::new(
+                    RendererInitErrorKind::NoAdapter,
+                    backends,
+                    "no WebGL2 adapter available",
+                )
+            })
+        }
+        BackendPreference::Auto => acquire_auto_adapter(canvas).await,
+    }
+}
+
+/// Automatic backend selection with the WebGPU→WebGL2 detection fallback.
+async fn acquire_auto_adapter(
+    canvas: Option<web_sys::HtmlCanvasElement>,
+) -> Result<(wgpu::Instance, wgpu::Adapter, Option<wgpu::Surface<'static>>), RendererInitError> {
     let backends = selected_backends();
 
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends,
-        flags: wgpu::InstanceFlags::default(),
-        dx12_shader_compiler: wgpu::Dx12Compiler::default(),
-    });
+    // When WebGPU and WebGL2 are both compiled in we probe WebGPU first and
+    // transparently fall back to the GL backend if it is unavailable, so a
+    // single wasm binary keeps working across browsers that lack `navigator.gpu`
+    // or cannot produce a WebGPU adapter.
+    let webgpu_requested = backends.contains(wgpu::Backends::BROWSER_WEBGPU);
+    let webgl2_available = backends.contains(wgpu::Backends::GL);
 
-    let adapter = instance
-        .request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::HighPerformance,
-            compatible_surface: None,
-            force_fallback_adapter: false,
-        })
+    if webgpu_requested && webgl2_available {
+        if is_browser_webgpu_supported() {
+            if let Some(selected) = select_adapter(wgpu::Backends::BROWSER_WEBGPU, &canvas).await? {
+                return Ok(selected);
+            }
+        }
+
+        return select_adapter(wgpu::Backends::GL, &canvas)
+            .await?
+            .ok_or_else(|| {
+                RendererInitError::new(
+                    RendererInitErrorKind::NoAdapter,
+                    backends,
+                    "WebGPU unavailable and no WebGL2 fallback adapter available",
+                )
+            });
+    }
+
+    // Single-backend builds fall through here. Guard the WebGPU-only case too,
+    // so a missing `navigator.gpu` becomes a structured `WebGpuUnavailable`
+    // rather than a raw JS exception from handing `BROWSER_WEBGPU` to `wgpu`.
+    if webgpu_requested && !webgl2_available && !is_browser_webgpu_supported() {
+        return Err(RendererInitError::new(
+            RendererInitErrorKind::WebGpuUnavailable,
+            backends,
+            "navigator.gpu is undefined; this browser does not support WebGPU",
+        ));
+    }
+
+    select_adapter(backends, &canvas).await?.ok_or_else(|| {
+        RendererInitError::new(
+            RendererInitErrorKind::NoAdapter,
+            backends,
+            "no compatible adapter available",
+        )
+    })
+}
+
+/// Builds an instance for `backends`, creates the optional canvas surface, and
+/// requests an adapter compatible with it.
+///
+/// Returns `Ok(None)` when no adapter is available (so the auto path can fall
+/// back to another backend) and `Err` only when surface creation fails.
+async fn select_adapter(
+    backends: wgpu::Backends,
+    canvas: &Option<web_sys::HtmlCanvasElement>,
+) -> Result<Option<(wgpu::Instance, wgpu::Adapter, Option<wgpu::Surface<'static>>)>, RendererInitError>
+{
+    let instance = new_instance(backends);
+    let surface = make_surface(&instance, canvas, backends)?;
+    Ok(request_adapter(&instance, surface.as_ref())
         .await
-        .ok_or_else(|| JsValue::from_str("No compatible WebGPU adapter found"))?;
+        .map(|adapter| (instance, adapter, surface)))
+}
+
+/// Optional device features we would like to enable. Intersected with what the
+/// adapter actually supports so the device request never asks for more than is
+/// available; currently empty, but kept explicit so it is easy to extend.
+const DESIRED_FEATURES: wgpu::Features = wgpu::Features::empty();
 
+/// Requests a device and queue from `adapter` and wraps everything in a
+/// [`Renderer`]. The negotiated limits start from WebGL2 downlevel defaults on
+/// the GL backend (and the standard defaults otherwise), then are clamped to
+/// the adapter's reported resolution so the request is always satisfiable.
+async fn build_renderer(
+    instance: wgpu::Instance,
+    adapter: wgpu::Adapter,
+    requested: &str,
+) -> Result<Renderer, RendererInitError> {
     let info = adapter.get_info();
-    let features = format!("{:?}", adapter.features());
 
-    Ok(RendererInfo {
+    let base_limits = if info.backend == wgpu::Backend::Gl {
+        wgpu::Limits::downlevel_webgl2_defaults()
+    } else {
+        wgpu::Limits::default()
+    };
+    let limits = base_limits.using_resolution(adapter.limits());
+    let features = DESIRED_FEATURES & adapter.features();
+
+    let (device, queue) = adapter
+        .request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("quickfix-renderer device"),
+                required_features: features,
+                required_limits: limits.clone(),
+            },
+            None,
+        )
+        .await
+        .map_err(|err| {
+            RendererInitError::new(
+                RendererInitErrorKind::DeviceRequestFailed,
+                wgpu::Backends::from(info.backend),
+                format!("failed to request device: {err}"),
+            )
+        })?;
+
+    let renderer_info = RendererInfo {
         backend: format!("{:?}", info.backend),
+        requested: requested.to_string(),
         adapter_name: info.name,
-        features,
+        device_type: format!("{:?}", info.device_type),
+        driver: info.driver,
+        features: format!("{features:?}"),
+        max_texture_dimension_2d: limits.max_texture_dimension_2d,
+        max_buffer_size: limits.max_buffer_size as f64,
+        surface_format: None,
+    };
+
+    Ok(Renderer {
+        instance,
+        adapter,
+        device,
+        queue,
+        surface: None,
+        info: renderer_info,
     })
 }
 
@@ -88,8 +609,47 @@ mod tests {
 
     #[wasm_bindgen_test]
     async fn initializes_renderer() {
-        let info = initialize_renderer().await.expect("renderer initializes");
+        let renderer = initialize_renderer().await.expect("renderer initializes");
+        let info = renderer.info();
         assert!(!info.backend().is_empty());
         assert!(!info.adapter_name().is_empty());
+        assert!(info.max_texture_dimension_2d() > 0);
+    }
+}
+
+#[cfg(test)]
+mod preference_tests {
+    use super::*;
+
+    fn parse(input: &str) -> BackendPreference {
+        parse_preference(Some(input.to_string()))
+    }
+
+    #[test]
+    fn webgpu_aliases_map_to_webgpu() {
+        assert_eq!(parse("webgpu"), BackendPreference::WebGpu);
+        assert_eq!(parse("gpu"), BackendPreference::WebGpu);
+    }
+
+    #[test]
+    fn webgl_aliases_map_to_webgl() {
+        for alias in ["webgl", "webgl2", "gl", "opengl", "gles"] {
+            assert_eq!(parse(alias), BackendPreference::WebGl, "alias {alias}");
+        }
+    }
+
+    #[test]
+    fn parsing_is_case_insensitive_and_trimmed() {
+        assert_eq!(parse("  WebGPU  "), BackendPreference::WebGpu);
+        assert_eq!(parse("\tOpenGL\n"), BackendPreference::WebGl);
+    }
+
+    #[test]
+    fn auto_empty_and_unknown_degrade_to_auto() {
+        assert_eq!(parse("auto"), BackendPreference::Auto);
+        assert_eq!(parse(""), BackendPreference::Auto);
+        assert_eq!(parse("   "), BackendPreference::Auto);
+        assert_eq!(parse("metal"), BackendPreference::Auto);
+        assert_eq!(parse_preference(None), BackendPreference::Auto);
     }
 }